@@ -1,7 +1,7 @@
 //![![ci-tests](https://github.com/arindas/bheap/actions/workflows/ci-tests.yml/badge.svg)](https://github.com/arindas/bheap/actions/workflows/ci-tests.yml)
 //![![rustdoc](https://github.com/arindas/bheap/actions/workflows/rustdoc.yml/badge.svg)](https://github.com/arindas/bheap/actions/workflows/rustdoc.yml)
 //!
-//!A generic binary max heap implementation for implementing a dynamically prioritizable priority queue.
+//!A generic binary heap implementation for implementing a dynamically prioritizable priority queue.
 //!
 //!This implementation uses a vector as the underlying data-structure. Hence, there is no oppurtunity
 //!for fine grained locking. Users of this crate are request to wrap `bheap::BinaryMaxHeap` with the
@@ -13,8 +13,11 @@
 //!for change in ordering of elements at runtime.
 //!
 //!## How does it work?
-//!`bheap::BinaryMaxHeap` enforces the `Ord + bheap::Uid` trait bounds on the element type. The `Uid` trait, simply
-//!presents a method for returing a unique `u64` uid for the type.
+//!`bheap::BinaryHeap<T, K>` enforces the `Ord + bheap::Uid` trait bounds on the element type. The `Uid` trait, simply
+//!presents a method for returing a unique `u64` uid for the type. The `K: Kind` marker type parameter selects whether
+//!`Ordering::Greater` (`Max`) or `Ordering::Less` (`Min`) is treated as higher-priority, without requiring callers to
+//!wrap elements in `std::cmp::Reverse`. `BinaryMaxHeap<T>` and `BinaryMinHeap<T>` are ready-made aliases for the two;
+//!a heap can also be built with a runtime-chosen comparator via `new_by`/`from_vec_by`.
 //!
 //!The struct maintains a `Vec<T>` as the underlying storage buffer and a `HashMap<u64, usize>` for maintaining a
 //!mapping from `T::uid()` to position in vector. This map is updated on every heap operation to remain consistent.
@@ -26,7 +29,12 @@
 //!Since, we use `u64` for uniquely identitfying elements, this heap can only scale up `2^64 = 18446744073709551616` elements.
 //!This was more than enough for my purposes.
 
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 /// Trait to uniquely identify elements in bheap.
 pub trait Uid {
@@ -35,30 +43,118 @@ pub trait Uid {
     fn uid(&self) -> u64;
 }
 
-/// A re-prioritizable binary max heap containing a buffer for storing elements
-/// and a hashmap index for keeping track of element positions.
-pub struct BinaryMaxHeap<T>
+/// Marker trait selecting the ordering direction used by the heapify routines.
+///
+/// `Kind::is_prioritized(ord)` is given the result of comparing element `i`
+/// against element `j`, and must return `true` if that means `i` outranks
+/// `j`, i.e. `i` should move towards the root.
+pub trait Kind {
+    /// Returns whether `ord` means the left-hand element outranks the
+    /// right-hand one for this kind of heap.
+    fn is_prioritized(ord: Ordering) -> bool;
+}
+
+/// [`Kind`] marker selecting max-heap ordering: the greatest element is the root.
+pub struct Max;
+
+/// [`Kind`] marker selecting min-heap ordering: the least element is the root.
+pub struct Min;
+
+impl Kind for Max {
+    #[inline]
+    fn is_prioritized(ord: Ordering) -> bool {
+        ord == Ordering::Greater
+    }
+}
+
+impl Kind for Min {
+    #[inline]
+    fn is_prioritized(ord: Ordering) -> bool {
+        ord == Ordering::Less
+    }
+}
+
+/// Boxed runtime comparator. `Send + Sync` so that a `BinaryHeap` built
+/// with one stays `Send`/`Sync` whenever `T` is, matching the heaps built
+/// without a comparator.
+type Comparator<T> = Box<dyn Fn(&T, &T) -> Ordering + Send + Sync>;
+
+/// A re-prioritizable binary heap containing a buffer for storing elements,
+/// a hashmap index for keeping track of element positions, and a `K: Kind`
+/// marker selecting max-heap or min-heap ordering.
+pub struct BinaryHeap<T, K = Max>
 where
     T: Ord + Uid,
+    K: Kind,
 {
     /// in-memory storage for elements
     buffer: Vec<T>,
 
     /// mapping from element uids to positions in the heap buffer
     index: HashMap<u64, usize>,
+
+    /// optional runtime-chosen comparator; when absent, `T::cmp` is used
+    comparator: Option<Comparator<T>>,
+
+    _kind: PhantomData<K>,
 }
 
-impl<T> BinaryMaxHeap<T>
+/// A binary heap where the greatest element (by `Ord` or a custom comparator) is the root.
+pub type BinaryMaxHeap<T> = BinaryHeap<T, Max>;
+
+/// A binary heap where the least element (by `Ord` or a custom comparator) is the root,
+/// without requiring elements to be wrapped in `std::cmp::Reverse`.
+pub type BinaryMinHeap<T> = BinaryHeap<T, Min>;
+
+/// Error returned by [`BinaryHeap::append`] when both heaps contain an
+/// element with the same uid. The index is keyed on uid uniqueness, so
+/// merging such heaps would silently drop one of the colliding entries.
+#[derive(Debug, PartialEq, Eq)]
+pub struct DuplicateUidError(pub u64);
+
+impl std::fmt::Display for DuplicateUidError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "both heaps contain an element with uid {}", self.0)
+    }
+}
+
+impl std::error::Error for DuplicateUidError {}
+
+impl<T, K> BinaryHeap<T, K>
 where
     T: Ord + Uid,
+    K: Kind,
 {
-    /// Creates a new vector from a given vector, which may or may not be
+    /// Creates a new heap from a given vector, which may or may not be
     /// empty. If the vector already contains elements, the elements are
     /// re-arranged with a `build_heap()` operation.
     pub fn from_vec(buffer: Vec<T>) -> Self {
-        let mut bheap = BinaryMaxHeap {
+        let mut bheap = BinaryHeap {
+            buffer,
+            index: HashMap::new(),
+            comparator: None,
+            _kind: PhantomData,
+        };
+
+        if !bheap.is_empty() {
+            bheap.build_heap();
+        }
+
+        bheap
+    }
+
+    /// Creates a new heap from a given vector, ordering elements with the
+    /// given `comparator` instead of `T::cmp`. This allows prioritizing on
+    /// a runtime-chosen key.
+    pub fn from_vec_by(
+        buffer: Vec<T>,
+        comparator: impl Fn(&T, &T) -> Ordering + Send + Sync + 'static,
+    ) -> Self {
+        let mut bheap = BinaryHeap {
             buffer,
             index: HashMap::new(),
+            comparator: Some(Box::new(comparator)),
+            _kind: PhantomData,
         };
 
         if !bheap.is_empty() {
@@ -68,9 +164,15 @@ where
         bheap
     }
 
-    /// Creates an empty binary max heap with no elements.
+    /// Creates an empty binary heap with no elements.
     pub fn new() -> Self {
-        BinaryMaxHeap::from_vec(vec![])
+        BinaryHeap::from_vec(vec![])
+    }
+
+    /// Creates an empty binary heap ordering elements with the given
+    /// `comparator` instead of `T::cmp`.
+    pub fn new_by(comparator: impl Fn(&T, &T) -> Ordering + Send + Sync + 'static) -> Self {
+        BinaryHeap::from_vec_by(vec![], comparator)
     }
 
     #[inline]
@@ -96,21 +198,34 @@ where
         self.buffer.swap(i, j);
     }
 
-    /// Convenience method for comparing elements at the given indices.
+    /// Convenience method for comparing elements at the given indices,
+    /// using the custom comparator if one was supplied, `T::cmp` otherwise.
     #[inline]
     fn cmp(&self, i: usize, j: usize) -> Ordering {
-        self.buffer[i].cmp(&self.buffer[j])
+        match &self.comparator {
+            Some(comparator) => comparator(&self.buffer[i], &self.buffer[j]),
+            None => self.buffer[i].cmp(&self.buffer[j]),
+        }
+    }
+
+    /// Returns whether the element at `i` outranks the element at `j`,
+    /// according to this heap's `Kind` and comparator. All heapify routines
+    /// route through this single helper, so `Max`, `Min` and
+    /// custom-comparator heaps share one code path.
+    #[inline]
+    fn ordered(&self, i: usize, j: usize) -> bool {
+        K::is_prioritized(self.cmp(i, j))
     }
 
     /// Restores heap property by moving the element in the given index
     /// upwards along it's parents to the root, until it has no parents
-    /// or it is <= to its parents.
+    /// or it no longer outranks its parent.
     /// It operates in the following manner:
     /// ```text
     /// heapify_up(heap, i) {
     ///     while i > 0 {
     ///         let parent = (i - 1) / 2;
-    ///         if heap[i] > heap[parent] {
+    ///         if ordered(heap, i, parent) {
     ///             swap(heap, i, parent)
     ///         } else { break; }
     ///     }
@@ -122,7 +237,7 @@ where
         while i > 0 {
             let parent = (i - 1) / 2;
 
-            if let Ordering::Greater = self.cmp(i, parent) {
+            if self.ordered(i, parent) {
                 self.swap_elems_at_indices(i, parent);
                 i = parent;
             } else {
@@ -139,44 +254,49 @@ where
 
     /// Restores heap property by moving the element at the given index,
     /// downwards along it's children, towards the leaves, until it
-    /// has no children or it is >= to its children.
+    /// has no children or it is outranked by none of its children.
     /// It operates in the following manner:
     /// ```text
     /// heapify_dn(heap, i) {
     ///     while i < len(heap) / 2 {
-    ///         let max = i;
+    ///         let top = i;
     ///         let lc, rc = 2 * i + 1, 2 * i + 2;
     ///
-    ///         if lc < len(heap) && heap[max] < lc { max = lc; }
-    ///         if rc < len(heao) && heap[max] < rc { max = rc; }
+    ///         if lc < len(heap) && ordered(heap, lc, top) { top = lc; }
+    ///         if rc < len(heap) && ordered(heap, rc, top) { top = rc; }
     ///
-    ///         if i != max { swap(heap, i, max); i = max; }
+    ///         if i != top { swap(heap, i, top); i = top; }
     ///         else { break; }
     ///     }
     /// }
     /// ```
     fn heapify_dn(&mut self, idx: usize) -> Option<usize> {
+        let bound = self.len();
+
+        self.heapify_dn_bounded(idx, bound)
+    }
+
+    /// Same as `heapify_dn`, but treats the buffer as if it were only
+    /// `bound` elements long. Used by `into_sorted_vec` to sift within the
+    /// shrinking unsorted prefix without touching the already-sorted suffix.
+    fn heapify_dn_bounded(&mut self, idx: usize, bound: usize) -> Option<usize> {
         let mut i = idx;
 
-        while i < (self.len() / 2) {
-            let mut max = i;
+        while i < (bound / 2) {
+            let mut top = i;
             let (lc, rc) = (2 * i + 1, 2 * i + 2);
 
-            if lc < self.len() {
-                if let Ordering::Less = self.cmp(max, lc) {
-                    max = lc;
-                }
+            if lc < bound && self.ordered(lc, top) {
+                top = lc;
             }
 
-            if rc < self.len() {
-                if let Ordering::Less = self.cmp(max, rc) {
-                    max = rc;
-                }
+            if rc < bound && self.ordered(rc, top) {
+                top = rc;
             }
 
-            if i != max {
-                self.swap_elems_at_indices(i, max);
-                i = max;
+            if i != top {
+                self.swap_elems_at_indices(i, top);
+                i = top;
             } else {
                 break;
             }
@@ -209,7 +329,9 @@ where
     /// heap position, if present. This implementation assumes
     /// that no mutation used with respect to the returned
     /// mutable reference, modifies the uid() property for the
-    /// element.
+    /// element. Prefer [`BinaryHeap::get_mut`], which restores heap
+    /// property automatically instead of relying on the caller to
+    /// remember to do so.
     pub fn get(&mut self, i: usize) -> Option<&mut T> {
         if i >= self.len() {
             return None;
@@ -218,6 +340,37 @@ where
         Some(&mut self.buffer[i])
     }
 
+    /// Returns a [`PeekMut`] guard for the root element (the element with
+    /// the highest priority), if present. Mutating through the guard and
+    /// then dropping it restores heap property automatically.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, K>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(PeekMut::new(self, 0))
+    }
+
+    /// Returns a [`PeekMut`] guard for the element at the given heap
+    /// position, if present. Mutating through the guard and then dropping
+    /// it restores heap property automatically.
+    pub fn get_mut(&mut self, i: usize) -> Option<PeekMut<'_, T, K>> {
+        if i >= self.len() {
+            return None;
+        }
+
+        Some(PeekMut::new(self, i))
+    }
+
+    /// Returns a [`PeekMut`] guard for the element with the given uid, if
+    /// present. Mutating through the guard and then dropping it restores
+    /// heap property automatically.
+    pub fn get_mut_by_uid(&mut self, uid: u64) -> Option<PeekMut<'_, T, K>> {
+        let idx = self.index_in_heap_from_uid(uid)?;
+
+        Some(PeekMut::new(self, idx))
+    }
+
     /// Pushes a new element in this priority queue.
     pub fn push(&mut self, elem: T) {
         let idx = self.buffer.len();
@@ -252,6 +405,43 @@ where
         Some(elem)
     }
 
+    /// Removes and returns the element with the given uid, if present, in
+    /// O(log n) by using the `HashMap` index for O(1) lookup followed by a
+    /// `swap_remove` and a heap-property restore at the vacated slot. This
+    /// is the "delete" half of decrease-key/delete, needed to use bheap
+    /// inside graph algorithms like Dijkstra and A*.
+    pub fn remove_by_uid(&mut self, uid: u64) -> Option<T> {
+        let idx = *self.index.get(&uid)?;
+
+        let elem = self.buffer.swap_remove(idx);
+        self.index.remove(&uid);
+
+        self.update_index(idx);
+        self.restore_heap_property(idx);
+
+        Some(elem)
+    }
+
+    /// Applies `f` to the element with the given uid, then restores heap
+    /// property and returns the element's new position, if present. `f`
+    /// must not change what `uid()` returns for the element; this is
+    /// asserted against after `f` runs. This is the "decrease-key" half of
+    /// decrease-key/delete, needed to use bheap inside graph algorithms
+    /// like Dijkstra and A*.
+    pub fn change_priority_by_uid(&mut self, uid: u64, f: impl FnOnce(&mut T)) -> Option<usize> {
+        let idx = *self.index.get(&uid)?;
+
+        f(&mut self.buffer[idx]);
+
+        assert_eq!(
+            self.buffer[idx].uid(),
+            uid,
+            "uid() must not change as a result of changing priority"
+        );
+
+        Some(self.restore_heap_property(idx).unwrap_or(idx))
+    }
+
     /// Builds the `HashMap` index from uids to buffer positions.
     pub fn build_index(&mut self) {
         for i in 0..self.len() {
@@ -296,11 +486,191 @@ where
     pub fn index_in_heap(&self, elem: &T) -> Option<usize> {
         self.index.get(&elem.uid()).map(|&elem_idx| elem_idx)
     }
+
+    /// Consumes the heap, returning the underlying buffer in arbitrary
+    /// (heap) order. This is O(1), as it requires no re-arrangement.
+    pub fn into_vec(self) -> Vec<T> {
+        self.buffer
+    }
+
+    /// Consumes the heap, returning a `Vec<T>` with elements arranged in
+    /// ascending order of priority (for `BinaryMaxHeap`, this is ascending
+    /// by `Ord`). This is the classic in-place heapsort: repeatedly swap
+    /// the root with the last unsorted element, then `heapify_dn` over the
+    /// shrinking unsorted prefix, for O(n log n) with no extra allocation.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.len()).rev() {
+            // `self` is consumed by this method, so the index is about to be
+            // discarded; a plain buffer swap avoids n wasted hashmap inserts.
+            self.buffer.swap(0, end);
+            self.heapify_dn_bounded(0, end);
+        }
+
+        self.buffer
+    }
+
+    /// Drains the heap in priority order, leaving it empty and its index
+    /// cleared once the returned iterator is exhausted. Equivalent to
+    /// repeatedly calling `pop()`.
+    pub fn drain_sorted(&mut self) -> impl Iterator<Item = T> + '_ {
+        std::iter::from_fn(move || self.pop())
+    }
+
+    /// Returns an iterator over the buffer, in arbitrary (heap) order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.buffer.iter()
+    }
+
+    /// Moves all elements out of `other` into `self`, leaving `other` empty.
+    ///
+    /// Fails with [`DuplicateUidError`] if both heaps share an element uid,
+    /// since the index is keyed on uid uniqueness and merging such heaps
+    /// would silently drop one of the colliding entries; in that case
+    /// neither heap is modified. Rebuilds with a single `build_heap()` when
+    /// `other` is at least as large as `self` (mirroring the heuristic used
+    /// by `Extend`), otherwise pushes `other`'s elements one by one.
+    pub fn append(&mut self, other: &mut Self) -> Result<(), DuplicateUidError> {
+        if let Some(&uid) = other.index.keys().find(|uid| self.index.contains_key(uid)) {
+            return Err(DuplicateUidError(uid));
+        }
+
+        let rebuild = other.len() >= self.len();
+        other.index.clear();
+
+        if rebuild {
+            self.buffer.append(&mut other.buffer);
+            self.build_heap();
+        } else {
+            for elem in other.buffer.drain(..) {
+                self.push(elem);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, K> FromIterator<T> for BinaryHeap<T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    /// Collects into a heap by building the buffer first and then calling
+    /// `build_heap()` once, rather than pushing element-by-element.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        BinaryHeap::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T, K> IntoIterator for BinaryHeap<T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Consumes the heap, yielding its elements in arbitrary (heap) order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.buffer.into_iter()
+    }
+}
+
+impl<T, K> Extend<T> for BinaryHeap<T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    /// Mirrors the amortization std's `Extend for BinaryHeap` uses: when at
+    /// least as many elements are being added as the heap already holds,
+    /// it's cheaper to append the whole batch and `build_heap()` once than
+    /// to `heapify_up` after every single push.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iterator = iter.into_iter();
+        let (lower, _) = iterator.size_hint();
+
+        if lower >= self.len() {
+            self.buffer.extend(iterator);
+            self.build_heap();
+        } else {
+            for elem in iterator {
+                self.push(elem);
+            }
+        }
+    }
+}
+
+/// A mutable guard for an element borrowed from a [`BinaryHeap`], obtained
+/// from [`BinaryHeap::peek_mut`], [`BinaryHeap::get_mut`] or
+/// [`BinaryHeap::get_mut_by_uid`].
+///
+/// `Deref` gives read-only access without marking the element modified.
+/// `DerefMut` marks it modified, so on `Drop` the guard restores heap
+/// property exactly once via `restore_heap_property`. This turns the
+/// previous "mutate then remember to call `restore_heap_property`
+/// yourself" sequence into a safe RAII API.
+pub struct PeekMut<'a, T, K = Max>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    heap: &'a mut BinaryHeap<T, K>,
+    idx: usize,
+    modified: bool,
+}
+
+impl<'a, T, K> PeekMut<'a, T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    fn new(heap: &'a mut BinaryHeap<T, K>, idx: usize) -> Self {
+        PeekMut {
+            heap,
+            idx,
+            modified: false,
+        }
+    }
+}
+
+impl<'a, T, K> Deref for PeekMut<'a, T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.buffer[self.idx]
+    }
+}
+
+impl<'a, T, K> DerefMut for PeekMut<'a, T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        self.modified = true;
+        &mut self.heap.buffer[self.idx]
+    }
+}
+
+impl<'a, T, K> Drop for PeekMut<'a, T, K>
+where
+    T: Ord + Uid,
+    K: Kind,
+{
+    fn drop(&mut self) {
+        if self.modified {
+            self.heap.restore_heap_property(self.idx);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{BinaryMaxHeap, Uid};
+    use crate::{BinaryMaxHeap, BinaryMinHeap, DuplicateUidError, Uid};
 
     impl Uid for u32 {
         fn uid(&self) -> u64 {
@@ -308,9 +678,22 @@ mod tests {
         }
     }
 
-    impl<T> BinaryMaxHeap<T>
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+    struct Item {
+        priority: i32,
+        uid: u64,
+    }
+
+    impl Uid for Item {
+        fn uid(&self) -> u64 {
+            self.uid
+        }
+    }
+
+    impl<T, K> crate::BinaryHeap<T, K>
     where
         T: Ord + Uid,
+        K: crate::Kind,
     {
         fn index_consistent(&self) -> bool {
             let mut result = true;
@@ -425,4 +808,216 @@ mod tests {
         assert_eq!(heap.pop(), None);
         assert!(heap.index_consistent());
     }
+
+    #[test]
+    fn binary_min_heap_from_vec_with_elems() {
+        let mut heap = BinaryMinHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+
+        assert_eq!(heap.peek(), Some(&1));
+        assert_eq!(heap.pop(), Some(1));
+        assert!(heap.index_consistent());
+
+        assert_eq!(heap.peek(), Some(&2));
+        assert_eq!(heap.pop(), Some(2));
+        assert!(heap.index_consistent());
+
+        assert_eq!(heap.peek(), Some(&5));
+        assert_eq!(heap.pop(), Some(5));
+        assert!(heap.index_consistent());
+
+        assert_eq!(heap.peek(), Some(&7));
+        assert_eq!(heap.pop(), Some(7));
+        assert!(heap.index_consistent());
+
+        assert_eq!(heap.peek(), Some(&9));
+        assert_eq!(heap.pop(), Some(9));
+        assert!(heap.index_consistent());
+
+        assert_eq!(heap.peek(), Some(&10));
+        assert_eq!(heap.pop(), Some(10));
+        assert!(heap.index_consistent());
+
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn binary_heap_with_custom_comparator() {
+        // Prioritize elements closer to 5, regardless of their natural `Ord`.
+        let mut heap = BinaryMaxHeap::from_vec_by(vec![1, 7, 2, 5, 10, 9], |a: &u32, b: &u32| {
+            let da = (*a as i64 - 5).abs();
+            let db = (*b as i64 - 5).abs();
+            db.cmp(&da)
+        });
+
+        assert_eq!(heap.peek(), Some(&5));
+        assert_eq!(heap.pop(), Some(5));
+        assert!(heap.index_consistent());
+    }
+
+    #[test]
+    fn peek_mut_restores_heap_property_on_drop() {
+        let mut heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+
+        {
+            let mut root = heap.peek_mut().unwrap();
+            *root = 0;
+        }
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek(), Some(&9));
+
+        {
+            let mut elem = heap.get_mut_by_uid(1).unwrap();
+            *elem = 100;
+        }
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek(), Some(&100));
+    }
+
+    #[test]
+    fn into_vec_and_into_sorted_vec() {
+        let heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+        let mut buffer = heap.into_vec();
+        buffer.sort();
+        assert_eq!(buffer, vec![1, 2, 5, 7, 9, 10]);
+
+        let heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+        assert_eq!(heap.into_sorted_vec(), vec![1, 2, 5, 7, 9, 10]);
+
+        let heap = BinaryMinHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+        assert_eq!(heap.into_sorted_vec(), vec![10, 9, 7, 5, 2, 1]);
+    }
+
+    #[test]
+    fn drain_sorted_empties_heap_and_index() {
+        let mut heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+
+        let drained: Vec<u32> = heap.drain_sorted().collect();
+        assert_eq!(drained, vec![10, 9, 7, 5, 2, 1]);
+
+        assert!(heap.is_empty());
+        assert_eq!(heap.index_in_heap_from_uid(1), None);
+    }
+
+    #[test]
+    fn from_iterator_and_into_iterator() {
+        let heap: BinaryMaxHeap<u32> = vec![1, 7, 2, 5, 10, 9].into_iter().collect();
+        assert!(heap.index_consistent());
+
+        let mut buffer: Vec<u32> = heap.into_iter().collect();
+        buffer.sort();
+        assert_eq!(buffer, vec![1, 2, 5, 7, 9, 10]);
+    }
+
+    #[test]
+    fn iter_over_buffer() {
+        let heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+
+        let mut seen: Vec<&u32> = heap.iter().collect();
+        seen.sort();
+        assert_eq!(seen, vec![&1, &2, &5, &7, &9, &10]);
+    }
+
+    #[test]
+    fn extend_keeps_index_consistent() {
+        let mut heap = BinaryMaxHeap::from_vec(vec![1, 7]);
+        heap.extend(vec![2, 5, 10, 9]);
+
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek(), Some(&10));
+        assert_eq!(heap.len(), 6);
+    }
+
+    #[test]
+    fn extend_with_fewer_elements_pushes_one_by_one() {
+        // fewer incoming elements than the heap already holds: exercises the
+        // per-element `push` path rather than the bulk `build_heap` rebuild.
+        let mut heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10]);
+        heap.extend(vec![20, 3]);
+
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek(), Some(&20));
+        assert_eq!(heap.len(), 7);
+    }
+
+    #[test]
+    fn remove_by_uid_restores_heap_property() {
+        let mut heap = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10, 9]);
+
+        assert_eq!(heap.remove_by_uid(5), Some(5));
+        assert!(heap.index_consistent());
+        assert_eq!(heap.len(), 5);
+        assert_eq!(heap.index_in_heap_from_uid(5), None);
+
+        assert_eq!(heap.peek(), Some(&10));
+        assert_eq!(heap.remove_by_uid(10), Some(10));
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek(), Some(&9));
+
+        assert_eq!(heap.remove_by_uid(42), None);
+    }
+
+    #[test]
+    fn change_priority_by_uid_restores_heap_property() {
+        let mut heap = BinaryMaxHeap::from_vec(
+            [1, 7, 2, 5, 10, 9]
+                .into_iter()
+                .enumerate()
+                .map(|(uid, priority)| Item {
+                    priority,
+                    uid: uid as u64,
+                })
+                .collect(),
+        );
+
+        // uid 0 holds priority 1; raise it above everything else.
+        let new_idx = heap.change_priority_by_uid(0, |elem| elem.priority = 20).unwrap();
+        assert_eq!(new_idx, 0);
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek().unwrap().priority, 20);
+
+        // uid 4 holds priority 10, now second only to uid 0; drop it to the bottom.
+        heap.change_priority_by_uid(4, |elem| elem.priority = -1);
+        assert!(heap.index_consistent());
+        assert_eq!(heap.peek().unwrap().priority, 20);
+
+        assert!(heap.change_priority_by_uid(42, |_| {}).is_none());
+    }
+
+    #[test]
+    fn append_merges_heaps() {
+        let mut a = BinaryMaxHeap::from_vec(vec![1, 7, 2]);
+        let mut b = BinaryMaxHeap::from_vec(vec![5, 10, 9]);
+
+        a.append(&mut b).unwrap();
+
+        assert!(b.is_empty());
+        assert!(a.index_consistent());
+        assert_eq!(a.into_sorted_vec(), vec![1, 2, 5, 7, 9, 10]);
+    }
+
+    #[test]
+    fn append_with_smaller_other_pushes_one_by_one() {
+        // `other` is smaller than `self`: exercises the per-element `push`
+        // path rather than the bulk `build_heap` rebuild.
+        let mut a = BinaryMaxHeap::from_vec(vec![1, 7, 2, 5, 10]);
+        let mut b = BinaryMaxHeap::from_vec(vec![20, 3]);
+
+        a.append(&mut b).unwrap();
+
+        assert!(b.is_empty());
+        assert!(a.index_consistent());
+        assert_eq!(a.into_sorted_vec(), vec![1, 2, 3, 5, 7, 10, 20]);
+    }
+
+    #[test]
+    fn append_rejects_duplicate_uids() {
+        let mut a = BinaryMaxHeap::from_vec(vec![1, 7, 2]);
+        let mut b = BinaryMaxHeap::from_vec(vec![7, 10, 9]);
+
+        assert_eq!(a.append(&mut b), Err(DuplicateUidError(7)));
+        // neither heap was modified
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 3);
+    }
 }